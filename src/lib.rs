@@ -2,16 +2,264 @@ use pgrx::pg_sys::JumbleState;
 use pgrx::prelude::*;
 use pgrx::pg_sys;
 use pgrx::guc::{GucContext, GucFlags, GucRegistry, GucSetting};
+use pgrx::PostgresGucEnum;
 
 ::pgrx::pg_module_magic!();
 
-// Store the previous hook to maintain the hook chain
+// Store the previous hooks to maintain the hook chains
 static mut PREV_POST_PARSE_ANALYZE_HOOK: pg_sys::post_parse_analyze_hook_type = None;
+static mut PREV_PROCESS_UTILITY_HOOK: pg_sys::ProcessUtility_hook_type = None;
 
 // GUC variable for pg_where_guard.enabled (default: true)
 static PG_WHERE_GUARD_ENABLED: GucSetting<bool> = GucSetting::<bool>::new(true);
 
+// GUC variables allowing DELETE and UPDATE to be guarded independently
+// (default: true) so an operator can enforce one statement type without
+// the other while rolling out the guard incrementally.
+static PG_WHERE_GUARD_REQUIRE_WHERE_DELETE: GucSetting<bool> = GucSetting::<bool>::new(true);
+static PG_WHERE_GUARD_REQUIRE_WHERE_UPDATE: GucSetting<bool> = GucSetting::<bool>::new(true);
+
+// GUC variable for pg_where_guard.reject_fishy_quals (default: false).
+// When enabled, a non-null WHERE clause that is semantically equivalent
+// to "no filter at all" (e.g. `WHERE 1=1` or `WHERE true`) is rejected
+// just like a missing WHERE clause.
+static PG_WHERE_GUARD_REJECT_FISHY_QUALS: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+/// Severity with which `where_checker` reacts to a guard violation.
+#[derive(PostgresGucEnum, Copy, Clone, Default, Eq, PartialEq)]
+enum PgWhereGuardSeverity {
+    #[default]
+    Error,
+    Warning,
+    Disabled,
+}
+
+// GUC variable for pg_where_guard.severity (default: error).
+static PG_WHERE_GUARD_SEVERITY: GucSetting<PgWhereGuardSeverity> =
+    GucSetting::<PgWhereGuardSeverity>::new(PgWhereGuardSeverity::Error);
+
+// GUC variable for pg_where_guard.error_hint (default: none).
+static PG_WHERE_GUARD_ERROR_HINT: GucSetting<Option<&'static str>> = GucSetting::<Option<&'static str>>::new(None);
+
+// GUC variable for pg_where_guard.allow_truncate (default: false). Lets
+// pg_where_guard act as a general destructive-statement guard rather than
+// only a WHERE-clause checker.
+static PG_WHERE_GUARD_ALLOW_TRUNCATE: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+// GUC variable for pg_where_guard.log_blocked (default: false). When
+// enabled, every rejection (and every warning emitted in `warning`
+// severity) is accompanied by a structured audit log line.
+static PG_WHERE_GUARD_LOG_BLOCKED: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+/// Emits a structured audit log line recording an offending statement:
+/// the command type, the current user's OID, the current database, and the
+/// source text exactly as passed in `query_text`. The text is not
+/// normalized: constants and whitespace appear as the client sent them.
+unsafe fn audit_log_blocked(command: &str, query_text: *const std::os::raw::c_char) {
+    let user_oid = pg_sys::GetUserId();
+
+    let db_name_ptr = pg_sys::get_database_name(pg_sys::MyDatabaseId);
+    let db_name = if db_name_ptr.is_null() {
+        "<unknown>".to_string()
+    } else {
+        std::ffi::CStr::from_ptr(db_name_ptr).to_string_lossy().into_owned()
+    };
+
+    let query = if query_text.is_null() {
+        "<unavailable>".to_string()
+    } else {
+        std::ffi::CStr::from_ptr(query_text).to_string_lossy().into_owned()
+    };
+
+    // This is an informational audit trail entry, not an error, so it gets
+    // the neutral "successful completion" SQLSTATE rather than the errcode
+    // of whatever violation triggered it.
+    ereport!(
+        LOG,
+        PgSqlErrorCode::ERRCODE_SUCCESSFUL_COMPLETION,
+        format!(
+            "pg_where_guard blocked {command}: user={user_oid} database={db_name} query={query}"
+        )
+    );
+}
+
 //pg_where_guard
+
+/// Raises the guard violation at the configured severity, appending the
+/// configured hint (if any) via `errhint`. In `Disabled` severity this is
+/// a no-op; in `Warning` severity the statement is still allowed to
+/// proceed after the warning is logged. `command` and `query_text` (the
+/// offending statement's source text, e.g. from `pstate->p_sourcetext`)
+/// are recorded to the audit log when `pg_where_guard.log_blocked` is on.
+unsafe fn report_violation(message: &str, command: &str, query_text: *const std::os::raw::c_char) {
+    let severity = PG_WHERE_GUARD_SEVERITY.get();
+    if matches!(severity, PgWhereGuardSeverity::Disabled) {
+        return;
+    }
+
+    if PG_WHERE_GUARD_LOG_BLOCKED.get() {
+        audit_log_blocked(command, query_text);
+    }
+
+    let level = match severity {
+        PgWhereGuardSeverity::Warning => PgLogLevel::WARNING,
+        _ => PgLogLevel::ERROR,
+    };
+
+    let mut report = PgErrorReport::new(PgSqlErrorCode::ERRCODE_CARDINALITY_VIOLATION, message, "");
+    if let Some(hint) = PG_WHERE_GUARD_ERROR_HINT.get() {
+        report = report.set_hint(hint);
+    }
+    report.report(level);
+}
+
+/// `expression_tree_walker` callback that records every `Var` node it
+/// visits into the `Vec<*mut Var>` passed as `context`. Delegating to
+/// PostgreSQL's own `expression_tree_walker` (rather than hand-rolling a
+/// dispatch over a handful of node types) means every expression shape
+/// the planner understands -- `ScalarArrayOpExpr` (`IN` lists), `FuncExpr`,
+/// `CoerceViaIO`, `SubLink`, `CaseExpr`, `CoalesceExpr`, and so on -- is
+/// walked correctly instead of being silently treated as "no columns".
+unsafe extern "C" fn var_collector_walker(
+    node: *mut pg_sys::Node,
+    context: *mut std::os::raw::c_void,
+) -> bool {
+    if node.is_null() {
+        return false;
+    }
+
+    if (*node).type_ == pg_sys::NodeTag::T_Var {
+        let vars = &mut *(context as *mut Vec<*mut pg_sys::Var>);
+        vars.push(node as *mut pg_sys::Var);
+        return false;
+    }
+
+    if (*node).type_ == pg_sys::NodeTag::T_SubLink {
+        // `expression_tree_walker` treats `sublink->subselect` as opaque --
+        // it walks `testexpr` (NULL for EXISTS/NOT EXISTS) and hands the
+        // subselect back to this callback, but a Query is not itself an
+        // expression node, so passing it on to `expression_tree_walker`
+        // below does not descend into it. A correlated subquery
+        // like `WHERE EXISTS (SELECT 1 FROM t2 WHERE t2.id = t.id)` very
+        // much references real columns, so descend into the subselect by
+        // hand before falling through to the default walk of `testexpr`.
+        let sublink = node as *mut pg_sys::SubLink;
+        let vars = &mut *(context as *mut Vec<*mut pg_sys::Var>);
+        collect_vars_from_query((*sublink).subselect as *mut pg_sys::Query, vars);
+    }
+
+    pg_sys::expression_tree_walker(node, Some(var_collector_walker), context)
+}
+
+/// Collects every `Var` node reachable from `node`. Used to tell whether
+/// a qual expression references any columns at all.
+unsafe fn collect_vars(node: *mut pg_sys::Node, vars: &mut Vec<*mut pg_sys::Var>) {
+    if node.is_null() {
+        return;
+    }
+    var_collector_walker(node, vars as *mut Vec<*mut pg_sys::Var> as *mut std::os::raw::c_void);
+}
+
+/// Collects every `Var` reachable from a sub-`Query`'s WHERE clause and
+/// target list (e.g. a `SubLink`'s `subselect`). Vars found here may carry
+/// a nonzero `varlevelsup` if they're correlated references to an outer
+/// query -- that's still a real column reference, just not a "zero
+/// columns referenced" qual, which is all `quals_are_fishy` needs to know.
+unsafe fn collect_vars_from_query(query: *mut pg_sys::Query, vars: &mut Vec<*mut pg_sys::Var>) {
+    if query.is_null() {
+        return;
+    }
+
+    let query_ref = &*query;
+    if !query_ref.jointree.is_null() {
+        let jointree = &*query_ref.jointree;
+        collect_vars(jointree.quals, vars);
+    }
+
+    pg_list_foreach::<pg_sys::TargetEntry, _>(query_ref.targetList, |te| {
+        collect_vars(te.expr as *mut pg_sys::Node, vars);
+    });
+}
+
+/// Returns true if `opno` is the name of an equality operator (`=`).
+unsafe fn is_equality_operator(opno: pg_sys::Oid) -> bool {
+    let name_ptr = pg_sys::get_opname(opno);
+    if name_ptr.is_null() {
+        return false;
+    }
+    let name = std::ffi::CStr::from_ptr(name_ptr).to_string_lossy();
+    name == "="
+}
+
+/// Returns true if `qual` is a trivially reflexive comparison, i.e. an
+/// `OpExpr` using an equality operator whose two operands are the same
+/// `Var` (e.g. `WHERE t.id = t.id`).
+unsafe fn is_reflexive_comparison(qual: *mut pg_sys::Node) -> bool {
+    if qual.is_null() || (*qual).type_ != pg_sys::NodeTag::T_OpExpr {
+        return false;
+    }
+
+    let op_expr = qual as *mut pg_sys::OpExpr;
+    if pg_sys::list_length((*op_expr).args) != 2 || !is_equality_operator((*op_expr).opno) {
+        return false;
+    }
+
+    let lhs = pg_sys::list_nth(((*op_expr).args) as *mut pg_sys::List, 0) as *mut pg_sys::Node;
+    let rhs = pg_sys::list_nth(((*op_expr).args) as *mut pg_sys::List, 1) as *mut pg_sys::Node;
+
+    if lhs.is_null() || rhs.is_null() || (*lhs).type_ != pg_sys::NodeTag::T_Var || (*rhs).type_ != pg_sys::NodeTag::T_Var {
+        return false;
+    }
+
+    let lhs_var = lhs as *mut pg_sys::Var;
+    let rhs_var = rhs as *mut pg_sys::Var;
+    // varlevelsup must match too: a `varno`/`varattno` pair only identifies
+    // the same column when both Vars are resolved against the same query
+    // level. Without this, a qual containing a correlated subquery's
+    // comparison could line up a Var from the subquery's range table with
+    // an unrelated one at the outer level that happens to share numbering.
+    (*lhs_var).varno == (*rhs_var).varno
+        && (*lhs_var).varattno == (*rhs_var).varattno
+        && (*lhs_var).varlevelsup == (*rhs_var).varlevelsup
+}
+
+/// Returns true if `quals` looks equivalent to "no filter". After
+/// const-folding via `eval_const_expressions`, this covers `WHERE true`/
+/// `WHERE 1=1` style tautologies, reflexive comparisons such as
+/// `t.id = t.id`, and any qual that references no columns at all. The
+/// last case is flagged even when the qual does not match every row
+/// (e.g. `WHERE random() < 0.5`).
+unsafe fn quals_are_fishy(quals: *mut pg_sys::Node) -> bool {
+    if quals.is_null() {
+        return false;
+    }
+
+    let folded = pg_sys::eval_const_expressions(std::ptr::null_mut(), quals);
+    if folded.is_null() {
+        return false;
+    }
+
+    if (*folded).type_ == pg_sys::NodeTag::T_Const {
+        let const_node = folded as *mut pg_sys::Const;
+        // A qual that const-folds straight to a boolean constant is fully
+        // decided right here: `true` matches every row (fishy), while
+        // `false` or `NULL` matches none and is the safest possible
+        // qualifier -- neither case should fall through to the
+        // zero-columns-referenced heuristic below, which would otherwise
+        // misread "matches nothing" as "matches everything".
+        return (*const_node).consttype == pg_sys::BOOLOID
+            && !(*const_node).constisnull
+            && (*const_node).constvalue != 0;
+    }
+
+    if is_reflexive_comparison(folded) {
+        return true;
+    }
+
+    let mut vars = Vec::new();
+    collect_vars(folded, &mut vars);
+    vars.is_empty()
+}
 unsafe fn pg_list_foreach<T, F>(list_ptr: *mut pg_sys::List, mut closure: F)
 where
     F: FnMut(&T),
@@ -66,27 +314,23 @@ unsafe extern "C-unwind" fn where_checker(
     match query_ref.commandType {
         pg_sys::CmdType::CMD_DELETE => {
             // Assert that jointree is not null (like in C code)
-            if !query_ref.jointree.is_null() {
+            if PG_WHERE_GUARD_REQUIRE_WHERE_DELETE.get() && !query_ref.jointree.is_null() {
                 let jointree = &*query_ref.jointree;
                 if jointree.quals.is_null() {
-                    ereport!(
-                        ERROR,
-                        PgSqlErrorCode::ERRCODE_CARDINALITY_VIOLATION,
-                        "DELETE requires a WHERE clause"
-                    );
+                    report_violation("DELETE requires a WHERE clause", "DELETE", (*pstate).p_sourcetext);
+                } else if PG_WHERE_GUARD_REJECT_FISHY_QUALS.get() && quals_are_fishy(jointree.quals) {
+                    report_violation("WHERE clause matches all rows", "DELETE", (*pstate).p_sourcetext);
                 }
             }
         }
         pg_sys::CmdType::CMD_UPDATE => {
             // Assert that jointree is not null (like in C code)
-            if !query_ref.jointree.is_null() {
+            if PG_WHERE_GUARD_REQUIRE_WHERE_UPDATE.get() && !query_ref.jointree.is_null() {
                 let jointree = &*query_ref.jointree;
                 if jointree.quals.is_null() {
-                    ereport!(
-                        ERROR,
-                        PgSqlErrorCode::ERRCODE_CARDINALITY_VIOLATION,
-                        "UPDATE requires a WHERE clause"
-                    );
+                    report_violation("UPDATE requires a WHERE clause", "UPDATE", (*pstate).p_sourcetext);
+                } else if PG_WHERE_GUARD_REJECT_FISHY_QUALS.get() && quals_are_fishy(jointree.quals) {
+                    report_violation("WHERE clause matches all rows", "UPDATE", (*pstate).p_sourcetext);
                 }
             }
         }
@@ -101,6 +345,43 @@ unsafe extern "C-unwind" fn where_checker(
     }
 }
 
+/// Hook function that rejects TRUNCATE unless `pg_where_guard.allow_truncate`
+/// is set. `post_parse_analyze_hook` never sees utility statements such as
+/// TRUNCATE, so this is handled separately via `ProcessUtility_hook`.
+#[pg_guard]
+unsafe extern "C-unwind" fn process_utility_guard(
+    pstmt: *mut pg_sys::PlannedStmt,
+    query_string: *const std::os::raw::c_char,
+    read_only_tree: bool,
+    context: pg_sys::ProcessUtilityContext,
+    params: pg_sys::ParamListInfo,
+    query_env: *mut pg_sys::QueryEnvironment,
+    dest: *mut pg_sys::DestReceiver,
+    qc: *mut pg_sys::QueryCompletion,
+) {
+    if PG_WHERE_GUARD_ENABLED.get() && !PG_WHERE_GUARD_ALLOW_TRUNCATE.get() && !pstmt.is_null() {
+        let utility_stmt = (*pstmt).utilityStmt;
+        if !utility_stmt.is_null() && (*utility_stmt).type_ == pg_sys::NodeTag::T_TruncateStmt {
+            report_violation("TRUNCATE is not allowed", "TRUNCATE", query_string);
+        }
+    }
+
+    if let Some(prev_hook) = PREV_PROCESS_UTILITY_HOOK {
+        prev_hook(pstmt, query_string, read_only_tree, context, params, query_env, dest, qc);
+    } else {
+        pg_sys::standard_ProcessUtility(
+            pstmt,
+            query_string,
+            read_only_tree,
+            context,
+            params,
+            query_env,
+            dest,
+            qc,
+        );
+    }
+}
+
 /// Extension initialization function (equivalent to _PG_init in C)
 #[pg_guard]
 pub unsafe extern "C-unwind" fn _PG_init() {
@@ -114,16 +395,85 @@ pub unsafe extern "C-unwind" fn _PG_init() {
         GucFlags::default(),
     );
 
-    // Store the previous hook and install our hook
+    // Register the per-statement-type GUCs so DELETE and UPDATE can be
+    // guarded independently of one another.
+    GucRegistry::define_bool_guc(
+        c"pg_where_guard.require_where_delete",
+        c"Require a WHERE clause on DELETE",
+        c"Reject DELETE statements that have no WHERE clause",
+        &PG_WHERE_GUARD_REQUIRE_WHERE_DELETE,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_bool_guc(
+        c"pg_where_guard.require_where_update",
+        c"Require a WHERE clause on UPDATE",
+        c"Reject UPDATE statements that have no WHERE clause",
+        &PG_WHERE_GUARD_REQUIRE_WHERE_UPDATE,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_bool_guc(
+        c"pg_where_guard.reject_fishy_quals",
+        c"Reject tautological WHERE clauses",
+        c"Reject DELETE/UPDATE statements whose WHERE clause is semantically equivalent to no filter, e.g. WHERE 1=1",
+        &PG_WHERE_GUARD_REJECT_FISHY_QUALS,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_enum_guc(
+        c"pg_where_guard.severity",
+        c"Severity of a guard violation",
+        c"error aborts the statement, warning logs but allows it, disabled turns off reporting entirely",
+        &PG_WHERE_GUARD_SEVERITY,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_string_guc(
+        c"pg_where_guard.error_hint",
+        c"Hint appended to a guard violation",
+        c"Custom text appended via errhint to guide developers toward the correct fix",
+        &PG_WHERE_GUARD_ERROR_HINT,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_bool_guc(
+        c"pg_where_guard.allow_truncate",
+        c"Allow TRUNCATE",
+        c"Permit TRUNCATE statements; when false they are rejected just like an unqualified DELETE",
+        &PG_WHERE_GUARD_ALLOW_TRUNCATE,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_bool_guc(
+        c"pg_where_guard.log_blocked",
+        c"Audit-log blocked statements",
+        c"Log the command type, user, database, and query text of every rejected (or warned-about) statement",
+        &PG_WHERE_GUARD_LOG_BLOCKED,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    // Store the previous hooks and install ours
     PREV_POST_PARSE_ANALYZE_HOOK = pg_sys::post_parse_analyze_hook;
     pg_sys::post_parse_analyze_hook = Some(where_checker);
+
+    PREV_PROCESS_UTILITY_HOOK = pg_sys::ProcessUtility_hook;
+    pg_sys::ProcessUtility_hook = Some(process_utility_guard);
 }
 
 /// Extension cleanup function
 #[pg_guard]
 pub unsafe extern "C-unwind" fn _PG_fini() {
-    // Restore the previous hook
+    // Restore the previous hooks
     pg_sys::post_parse_analyze_hook = PREV_POST_PARSE_ANALYZE_HOOK;
+    pg_sys::ProcessUtility_hook = PREV_PROCESS_UTILITY_HOOK;
 }
 
 /// Function to check if pg_where_guard is enabled
@@ -204,6 +554,190 @@ mod tests {
         
         assert!(result.is_err(), "UPDATE without WHERE should fail when pg_where_guard is enabled");
     }
+
+    #[pg_test]
+    fn test_delete_without_where_allowed_when_require_where_delete_off() {
+        Spi::run("CREATE TEMP TABLE test_table3 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table3 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.require_where_delete = false").unwrap();
+
+        let result = Spi::run("DELETE FROM test_table3");
+        assert!(result.is_ok(), "DELETE without WHERE should succeed when require_where_delete is off");
+
+        Spi::run("SET pg_where_guard.require_where_delete = true").unwrap();
+    }
+
+    #[pg_test]
+    fn test_update_without_where_allowed_when_require_where_update_off() {
+        Spi::run("CREATE TEMP TABLE test_table4 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table4 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.require_where_update = false").unwrap();
+
+        let result = Spi::run("UPDATE test_table4 SET name = 'updated'");
+        assert!(result.is_ok(), "UPDATE without WHERE should succeed when require_where_update is off");
+
+        Spi::run("SET pg_where_guard.require_where_update = true").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_with_tautological_where_should_fail_when_reject_fishy_quals_on() {
+        Spi::run("CREATE TEMP TABLE test_table5 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table5 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.reject_fishy_quals = true").unwrap();
+
+        let result = std::panic::catch_unwind(|| {
+            Spi::run("DELETE FROM test_table5 WHERE 1=1").unwrap();
+        });
+
+        assert!(result.is_err(), "DELETE WHERE 1=1 should fail when reject_fishy_quals is on");
+
+        Spi::run("SET pg_where_guard.reject_fishy_quals = false").unwrap();
+    }
+
+    #[pg_test]
+    fn test_update_with_true_where_should_fail_when_reject_fishy_quals_on() {
+        Spi::run("CREATE TEMP TABLE test_table6 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table6 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.reject_fishy_quals = true").unwrap();
+
+        let result = std::panic::catch_unwind(|| {
+            Spi::run("UPDATE test_table6 SET name = 'updated' WHERE true").unwrap();
+        });
+
+        assert!(result.is_err(), "UPDATE WHERE true should fail when reject_fishy_quals is on");
+
+        Spi::run("SET pg_where_guard.reject_fishy_quals = false").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_with_real_where_should_succeed_when_reject_fishy_quals_on() {
+        Spi::run("CREATE TEMP TABLE test_table7 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table7 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.reject_fishy_quals = true").unwrap();
+
+        let result = Spi::run("DELETE FROM test_table7 WHERE id = 1");
+        assert!(result.is_ok(), "DELETE with a real filter should succeed when reject_fishy_quals is on");
+
+        Spi::run("SET pg_where_guard.reject_fishy_quals = false").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_with_in_list_where_should_succeed_when_reject_fishy_quals_on() {
+        Spi::run("CREATE TEMP TABLE test_table_in (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table_in VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.reject_fishy_quals = true").unwrap();
+
+        let result = Spi::run("DELETE FROM test_table_in WHERE id IN (1, 2, 3)");
+        assert!(result.is_ok(), "DELETE with an IN-list filter should succeed when reject_fishy_quals is on");
+
+        Spi::run("SET pg_where_guard.reject_fishy_quals = false").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_with_function_wrapped_where_should_succeed_when_reject_fishy_quals_on() {
+        Spi::run("CREATE TEMP TABLE test_table_fn (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table_fn VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.reject_fishy_quals = true").unwrap();
+
+        let result = Spi::run("DELETE FROM test_table_fn WHERE abs(id) = 1");
+        assert!(result.is_ok(), "DELETE with a function-wrapped filter should succeed when reject_fishy_quals is on");
+
+        Spi::run("SET pg_where_guard.reject_fishy_quals = false").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_with_false_where_should_succeed_when_reject_fishy_quals_on() {
+        Spi::run("CREATE TEMP TABLE test_table_false (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table_false VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.reject_fishy_quals = true").unwrap();
+
+        let result = Spi::run("DELETE FROM test_table_false WHERE 1=0");
+        assert!(result.is_ok(), "DELETE WHERE 1=0 matches no rows and should succeed when reject_fishy_quals is on");
+
+        Spi::run("SET pg_where_guard.reject_fishy_quals = false").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_with_correlated_exists_where_should_succeed_when_reject_fishy_quals_on() {
+        Spi::run("CREATE TEMP TABLE orders (id int, flagged_id int)").unwrap();
+        Spi::run("CREATE TEMP TABLE flagged (order_id int)").unwrap();
+        Spi::run("INSERT INTO orders VALUES (1, 1)").unwrap();
+        Spi::run("INSERT INTO flagged VALUES (1)").unwrap();
+        Spi::run("SET pg_where_guard.reject_fishy_quals = true").unwrap();
+
+        let result = Spi::run(
+            "DELETE FROM orders WHERE EXISTS (SELECT 1 FROM flagged fc WHERE fc.order_id = orders.id)",
+        );
+        assert!(
+            result.is_ok(),
+            "DELETE with a correlated EXISTS filter should succeed when reject_fishy_quals is on"
+        );
+
+        Spi::run("SET pg_where_guard.reject_fishy_quals = false").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_without_where_allowed_when_severity_warning() {
+        Spi::run("CREATE TEMP TABLE test_table8 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table8 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.severity = 'warning'").unwrap();
+
+        let result = Spi::run("DELETE FROM test_table8");
+        assert!(result.is_ok(), "DELETE without WHERE should only warn, not abort, when severity is warning");
+
+        Spi::run("SET pg_where_guard.severity = 'error'").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_without_where_allowed_when_severity_disabled() {
+        Spi::run("CREATE TEMP TABLE test_table9 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table9 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.severity = 'disabled'").unwrap();
+
+        let result = Spi::run("DELETE FROM test_table9");
+        assert!(result.is_ok(), "DELETE without WHERE should be allowed when severity is disabled");
+
+        Spi::run("SET pg_where_guard.severity = 'error'").unwrap();
+    }
+
+    #[pg_test]
+    fn test_truncate_should_fail_by_default() {
+        Spi::run("CREATE TEMP TABLE test_table10 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table10 VALUES (1, 'test')").unwrap();
+
+        let result = std::panic::catch_unwind(|| {
+            Spi::run("TRUNCATE test_table10").unwrap();
+        });
+
+        assert!(result.is_err(), "TRUNCATE should fail when allow_truncate is off");
+    }
+
+    #[pg_test]
+    fn test_truncate_should_succeed_when_allow_truncate_on() {
+        Spi::run("CREATE TEMP TABLE test_table11 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table11 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.allow_truncate = true").unwrap();
+
+        let result = Spi::run("TRUNCATE test_table11");
+        assert!(result.is_ok(), "TRUNCATE should succeed when allow_truncate is on");
+
+        Spi::run("SET pg_where_guard.allow_truncate = false").unwrap();
+    }
+
+    #[pg_test]
+    fn test_delete_without_where_still_fails_when_log_blocked_on() {
+        Spi::run("CREATE TEMP TABLE test_table12 (id int, name text)").unwrap();
+        Spi::run("INSERT INTO test_table12 VALUES (1, 'test')").unwrap();
+        Spi::run("SET pg_where_guard.log_blocked = true").unwrap();
+
+        let result = std::panic::catch_unwind(|| {
+            Spi::run("DELETE FROM test_table12").unwrap();
+        });
+
+        assert!(result.is_err(), "DELETE without WHERE should still fail with log_blocked on");
+
+        Spi::run("SET pg_where_guard.log_blocked = false").unwrap();
+    }
 }
 
 /// This module is required by `cargo pgrx test` invocations.